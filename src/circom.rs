@@ -0,0 +1,151 @@
+use crate::error::ZkError;
+use crate::field::FieldElement;
+use crate::r1cs::R1CS;
+use num_bigint::BigInt;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::str::FromStr;
+
+/// The `CircuitJson` layout emitted by circom's R1CS export: each
+/// constraint is `[A, B, C]`, where `A`/`B`/`C` are maps of
+/// `variable-index-string -> coefficient-string` encoding a linear
+/// combination `sum_i coeff_i * w_i`.
+#[derive(Deserialize)]
+struct CircomCircuit {
+    constraints: Vec<[HashMap<String, String>; 3]>,
+    #[serde(rename = "nPubInputs")]
+    n_pub_inputs: usize,
+    #[serde(rename = "nOutputs")]
+    n_outputs: usize,
+    #[serde(rename = "nVars")]
+    n_vars: usize,
+}
+
+impl R1CS {
+    /// Imports a circom circuit (`circuit_path`, the `CircuitJson` layout
+    /// above) and a matching witness file (`witness_path`, a JSON array of
+    /// decimal witness values in variable order) into an `R1CS` ready for
+    /// `verify_witness` and proof generation.
+    ///
+    /// Outputs are counted as public alongside the declared public inputs,
+    /// matching circom's convention that a circuit's outputs are part of
+    /// its public statement. circom's witness layout is
+    /// `[1, outputs.., public_inputs.., private..]`, so the public range
+    /// also includes index `0` — the constant `1` wire, which circom
+    /// always treats as known to the verifier — not just
+    /// `n_outputs + n_pub_inputs` values starting at index `0`.
+    ///
+    /// Both inputs come from external circom tooling rather than this
+    /// crate's own proving pipeline, so every parsing step returns
+    /// `ZkError` instead of panicking on a malformed or mismatched export.
+    pub fn from_circom_json(circuit_path: &str, witness_path: &str) -> Result<R1CS, ZkError> {
+        let circuit_data = fs::read_to_string(circuit_path)?;
+        let circuit: CircomCircuit = serde_json::from_str(&circuit_data)
+            .map_err(|err| ZkError::CircomImport(format!("failed to parse circuit JSON: {}", err)))?;
+
+        let witness_data = fs::read_to_string(witness_path)?;
+        let witness: Vec<String> = serde_json::from_str(&witness_data)
+            .map_err(|err| ZkError::CircomImport(format!("failed to parse witness JSON: {}", err)))?;
+        if witness.len() != circuit.n_vars {
+            return Err(ZkError::CircomImport(format!(
+                "witness has {} entries, expected nVars = {}",
+                witness.len(),
+                circuit.n_vars
+            )));
+        }
+
+        let mut r1cs = R1CS::new();
+        for value in &witness {
+            let parsed = BigInt::from_str(value)
+                .map_err(|_| ZkError::CircomImport(format!("witness entry '{}' is not a decimal integer", value)))?;
+            r1cs.add_variable(FieldElement::new(parsed));
+        }
+        r1cs.num_public_inputs = 1 + circuit.n_outputs + circuit.n_pub_inputs;
+
+        for [a, b, c] in &circuit.constraints {
+            let (a, b, c) = (linear_combination(a)?, linear_combination(b)?, linear_combination(c)?);
+            if let Some((index, _)) = a.iter().chain(&b).chain(&c).find(|(index, _)| *index >= circuit.n_vars) {
+                return Err(ZkError::CircomImport(format!(
+                    "circom variable index {} is out of range for nVars = {}",
+                    index, circuit.n_vars
+                )));
+            }
+
+            // `R1CS::add_constraint` builds the matching `Constraint` entry
+            // itself, so imported circuits get the same bookkeeping (and
+            // the same FFT-based QAP satisfiability check) as circuits
+            // built through `Circuit`.
+            r1cs.add_constraint(&a, &b, &c, &BigInt::from(0));
+        }
+
+        Ok(r1cs)
+    }
+}
+
+/// Builds a sparse linear combination `Vec<(usize, FieldElement)>` from a
+/// circom `{ "index": "coeff" }` map.
+fn linear_combination(terms: &HashMap<String, String>) -> Result<Vec<(usize, FieldElement)>, ZkError> {
+    terms
+        .iter()
+        .map(|(index, coeff)| {
+            let index: usize = index
+                .parse()
+                .map_err(|_| ZkError::CircomImport(format!("circom variable index '{}' is not a number", index)))?;
+            let coeff = BigInt::from_str(coeff)
+                .map_err(|_| ZkError::CircomImport(format!("circom coefficient '{}' is not a decimal integer", coeff)))?;
+            Ok((index, FieldElement::new(coeff)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `x*x = y` circuit in circom's export layout: `[1, y, x, p]`,
+    /// where `y` is the declared output, `x` the declared public input,
+    /// and `p` an unconstrained private variable — present so the public
+    /// range's upper edge is actually exercised by the test below.
+    fn write_fixture(dir: &std::path::Path) -> (String, String) {
+        let circuit_json = r#"{
+            "constraints": [[{"2": "1"}, {"2": "1"}, {"1": "1"}]],
+            "nPubInputs": 1,
+            "nOutputs": 1,
+            "nVars": 4
+        }"#;
+        let witness_json = r#"["1", "9", "3", "42"]"#;
+
+        let circuit_path = dir.join("circuit.json");
+        let witness_path = dir.join("witness.json");
+        fs::write(&circuit_path, circuit_json).unwrap();
+        fs::write(&witness_path, witness_json).unwrap();
+        (circuit_path.to_str().unwrap().to_string(), witness_path.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn imports_circuit_and_marks_the_right_variables_public() {
+        let dir = std::env::temp_dir().join("zk_mini_circom_import_test");
+        fs::create_dir_all(&dir).unwrap();
+        let (circuit_path, witness_path) = write_fixture(&dir);
+
+        let r1cs = R1CS::from_circom_json(&circuit_path, &witness_path).unwrap();
+
+        // `1`, `y`, `x` (indices 0..3) are public; `p` (index 3) is not.
+        assert_eq!(r1cs.num_public_inputs, 3);
+        assert!(r1cs.verify_witness(&r1cs.generate_witness()).is_ok());
+    }
+
+    #[test]
+    fn rejects_witness_length_mismatch_instead_of_panicking() {
+        let dir = std::env::temp_dir().join("zk_mini_circom_import_mismatch_test");
+        fs::create_dir_all(&dir).unwrap();
+        let (circuit_path, _witness_path) = write_fixture(&dir);
+
+        let bad_witness_path = dir.join("short_witness.json");
+        fs::write(&bad_witness_path, r#"["1", "9"]"#).unwrap();
+
+        let result = R1CS::from_circom_json(&circuit_path, bad_witness_path.to_str().unwrap());
+        assert!(matches!(result, Err(ZkError::CircomImport(_))));
+    }
+}
@@ -1,7 +1,9 @@
 use num_bigint::BigInt;
+use crate::error::ZkError;
+use crate::pairing;
 use crate::r1cs::{R1CS};
 use crate::field::FieldElement;
-use crate::proof::Proof;
+use crate::proof::{self, Proof, VerifyingKey};
 
 pub enum Gate {
     Add(usize, usize, usize), // Add: input1, input2, output
@@ -45,11 +47,12 @@ impl Circuit {
         self.inputs.get(index)
     }
 
-    /// Generates the proof and checks constraint satisfaction, then saves it to a binary file
-    pub fn generate_proof(&self, proof_file: &str) {
+    /// Runs the Groth16 setup/prove pipeline for the current circuit and
+    /// saves the resulting verifying key and constant-size proof to disk.
+    pub fn generate_proof(&self, proof_file: &str) -> Result<(), ZkError> {
         // Ensure inputs are added before generating proof
         if self.inputs.is_empty() {
-            panic!("No inputs available to generate proof.");
+            return Err(ZkError::EmptyCircuit);
         }
 
         let mut r1cs = R1CS::new();
@@ -59,67 +62,85 @@ impl Circuit {
             r1cs.add_variable(input.clone()); // input is of type FieldElement
         }
 
+        // A constant wire, so gates that need to multiply by a bare
+        // constant (like the `1` on the right-hand side of an Add gate's
+        // lowering below) have a variable to reference.
+        let one = r1cs.add_variable(FieldElement::one());
+
+        // A gate wired to an input that was never added is an error, not
+        // an out-of-range index into `r1cs.variables` below.
+        for gate in &self.gates {
+            let (a, b, output) = match gate {
+                Gate::Add(a, b, output) | Gate::Mul(a, b, output) => (*a, *b, *output),
+            };
+            if let Some(&index) = [a, b, output].iter().find(|&&i| i >= self.inputs.len()) {
+                return Err(ZkError::MissingInput { index });
+            }
+        }
+
         // Process each gate and add constraints to R1CS
         for gate in &self.gates {
             match gate {
+                // `a + b = out` has no native R1CS multiplication shape, so
+                // it's lowered as `(w_a + w_b) · 1 = w_out`.
                 Gate::Add(a, b, output) => {
                     r1cs.add_constraint(
                         &[
-                            (r1cs.variables[*a].index, FieldElement::new(BigInt::from(1))), // Extract index
-                        ],
-                        &[
-                            (r1cs.variables[*b].index, FieldElement::new(BigInt::from(1))), // Extract index
-                        ],
-                        &[
-                            (r1cs.variables[*output].index, FieldElement::new(BigInt::from(1))), // Extract index
+                            (r1cs.variables[*a].index, FieldElement::one()),
+                            (r1cs.variables[*b].index, FieldElement::one()),
                         ],
+                        &[(one, FieldElement::one())],
+                        &[(r1cs.variables[*output].index, FieldElement::one())],
                         &self.modulus, // Pass modulus dynamically
                     );
                 },
+                // `a * b = out` is already the native R1CS shape.
                 Gate::Mul(a, b, output) => {
                     r1cs.add_constraint(
-                        &[
-                            (r1cs.variables[*a].index, FieldElement::new(BigInt::from(1))), // Extract index
-                        ],
-                        &[
-                            (r1cs.variables[*b].index, FieldElement::new(BigInt::from(1))), // Extract index
-                        ],
-                        &[
-                            (r1cs.variables[*output].index, FieldElement::new(BigInt::from(1))), // Extract index
-                        ],
+                        &[(r1cs.variables[*a].index, FieldElement::one())],
+                        &[(r1cs.variables[*b].index, FieldElement::one())],
+                        &[(r1cs.variables[*output].index, FieldElement::one())],
                         &self.modulus, // Pass modulus dynamically
                     );
                 },
             }
         }
 
-        // Save the R1CS to a binary file
-        r1cs.save_to_binary("r1cs_file.bin");
-
-        // Generate the witness and proof
+        // Reject a bad witness here, with the constraint index that fails,
+        // rather than letting it reach `proof::prove`, where
+        // `QAP::quotient`'s vanishing-polynomial division only reports
+        // `ZkError::QuotientNotExact` without saying which constraint failed.
         let witness = r1cs.generate_witness();
-        let proof = r1cs.generate_proof(&witness);
-
-        // Save the proof to a specified file
-        proof.save_to_binary(proof_file).expect("failed to save the proof");
+        r1cs.verify_witness(&witness)?;
+
+        // Run the trusted setup for this R1CS's QAP, then prove the
+        // witness against the resulting proving key. See
+        // `pairing::InsecureDemoBackend` for why this needs a token.
+        let backend = pairing::InsecureDemoBackend::acknowledge_no_cryptographic_hardness();
+        let (proving_key, verifying_key) = proof::setup(&backend, &r1cs);
+        let groth_proof = proof::prove(&backend, &proving_key, &r1cs, &witness)?;
+
+        // Persist the verifying key alongside the proof; the verifier no
+        // longer needs the R1CS or the witness at all.
+        verifying_key.save_to_binary("vk_file.bin")?;
+        groth_proof.save_to_binary(proof_file)?;
+        Ok(())
     }
 
-    /// Verifies the proof by reading from a binary file
-    pub fn verify_proof(&self, proof_file: &str) -> bool {
-        let proof_data = std::fs::read(proof_file).expect("Could not read proof file");
-
-        let proof = bincode::deserialize::<Proof>(&proof_data).expect("Failed to deserialize proof");
-
-        // Ensure that witness is Vec<FieldElement> and not Vec<BigInt>
-        let witness: Vec<FieldElement> = proof.witness.iter()
-            .map(|value| FieldElement::new(value.clone()))
-            .collect();
+    /// Verifies a proof by reading it and the matching verifying key from
+    /// disk and checking the single Groth16 pairing equation. This is
+    /// constant-time in the number of constraints.
+    pub fn verify_proof(&self, proof_file: &str) -> Result<bool, ZkError> {
+        let proof_data = std::fs::read(proof_file)?;
+        let groth_proof = bincode::deserialize::<Proof>(&proof_data)?;
 
-        let r1cs = R1CS::load_from_binary("r1cs_file.bin");
+        let verifying_key = VerifyingKey::load_from_binary("vk_file.bin")?;
 
-        let is_valid = r1cs.verify_witness(&witness);
+        // No public inputs are tracked by this front-end yet.
+        let backend = pairing::InsecureDemoBackend::acknowledge_no_cryptographic_hardness();
+        let is_valid = proof::verify(&backend, &verifying_key, &[], &groth_proof);
 
         println!("Proof verification result: {}", is_valid);
-        is_valid
+        Ok(is_valid)
     }
 }
\ No newline at end of file
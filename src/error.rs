@@ -0,0 +1,62 @@
+use std::fmt;
+
+/// The crate's error type. Every fallible public API returns
+/// `Result<_, ZkError>` instead of panicking, so this library can be
+/// embedded in a long-running process without process-killing panics.
+#[derive(Debug)]
+pub enum ZkError {
+    Io(std::io::Error),
+    Serialization(bincode::Error),
+    /// A `Circuit` was asked to generate a proof before any inputs were added.
+    EmptyCircuit,
+    /// A witness doesn't have one entry per `R1CS` variable.
+    WitnessLengthMismatch { expected: usize, actual: usize },
+    /// The witness fails the constraint at this index of `R1CS::constraints`.
+    UnsatisfiedConstraint { index: usize },
+    /// No input was allocated at this index of a `Circuit`.
+    MissingInput { index: usize },
+    /// A circom circuit/witness JSON export was malformed or didn't match
+    /// expectations: bad JSON, a witness length mismatch, a non-decimal
+    /// variable index/coefficient, or a variable index outside `nVars`.
+    CircomImport(String),
+    /// `A(x)*B(x) - C(x)` didn't vanish on the evaluation domain, i.e. it
+    /// wasn't a multiple of `Z(x)`. Since a witness satisfying every
+    /// constraint always makes this hold exactly, this means the witness
+    /// passed to `QAP::quotient` fails the R1CS it was built from.
+    QuotientNotExact,
+}
+
+impl fmt::Display for ZkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZkError::Io(err) => write!(f, "I/O error: {}", err),
+            ZkError::Serialization(err) => write!(f, "serialization error: {}", err),
+            ZkError::EmptyCircuit => write!(f, "no inputs available to generate a proof"),
+            ZkError::WitnessLengthMismatch { expected, actual } => {
+                write!(f, "witness has {} entries, expected {}", actual, expected)
+            }
+            ZkError::UnsatisfiedConstraint { index } => {
+                write!(f, "constraint {} is not satisfied by the witness", index)
+            }
+            ZkError::MissingInput { index } => write!(f, "no input allocated at index {}", index),
+            ZkError::CircomImport(message) => write!(f, "circom import error: {}", message),
+            ZkError::QuotientNotExact => {
+                write!(f, "A(x)*B(x) - C(x) is not divisible by the vanishing polynomial")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ZkError {}
+
+impl From<std::io::Error> for ZkError {
+    fn from(err: std::io::Error) -> Self {
+        ZkError::Io(err)
+    }
+}
+
+impl From<bincode::Error> for ZkError {
+    fn from(err: bincode::Error) -> Self {
+        ZkError::Serialization(err)
+    }
+}
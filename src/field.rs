@@ -0,0 +1,147 @@
+use num_bigint::BigInt;
+use num_traits::{One, Signed, Zero};
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+/// The BN254 (alt_bn128) scalar field modulus, the pairing-friendly curve's
+/// `Fr`. Matches the curve used by the `pairing`/`proof` Groth16 backend.
+fn modulus() -> BigInt {
+    BigInt::parse_bytes(
+        b"21888242871839275222246405745257275088548364400416034343698204186575808495617",
+        10,
+    )
+    .unwrap()
+}
+
+/// A generator of `Fr^*`, used to derive roots of unity for the FFT
+/// evaluation domain.
+fn generator() -> BigInt {
+    BigInt::from(5u64)
+}
+
+/// `2^TWO_ADICITY` divides `p - 1`, so `Fr^*` has a subgroup of that
+/// order and every power-of-two evaluation domain up to this size has a
+/// primitive root of unity.
+const TWO_ADICITY: u32 = 28;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FieldElement {
+    value: BigInt,
+}
+
+impl FieldElement {
+    pub fn new(value: BigInt) -> Self {
+        let m = modulus();
+        let mut v = value % &m;
+        if v.is_negative() {
+            v += &m;
+        }
+        FieldElement { value: v }
+    }
+
+    pub fn zero() -> Self {
+        FieldElement::new(BigInt::zero())
+    }
+
+    pub fn one() -> Self {
+        FieldElement::new(BigInt::one())
+    }
+
+    pub fn get_value(&self) -> BigInt {
+        self.value.clone()
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        FieldElement::new(&self.value + &other.value)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        FieldElement::new(&self.value - &other.value)
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        FieldElement::new(&self.value * &other.value)
+    }
+
+    pub fn neg(&self) -> Self {
+        FieldElement::new(-&self.value)
+    }
+
+    pub fn pow(&self, exponent: &BigInt) -> Self {
+        FieldElement::new(self.value.modpow(exponent, &modulus()))
+    }
+
+    /// Multiplicative inverse via Fermat's little theorem: `a^(p-2) = a^-1`.
+    pub fn inverse(&self) -> Self {
+        self.pow(&(modulus() - BigInt::from(2u64)))
+    }
+
+    /// `log2` of the largest power-of-two subgroup of `F_p^*`.
+    pub fn two_adicity() -> u32 {
+        TWO_ADICITY
+    }
+
+    /// A primitive `n`-th root of unity, where `n` must be a power of two
+    /// no larger than `2^two_adicity()`.
+    pub fn root_of_unity(n: u64) -> Self {
+        assert!(n.is_power_of_two(), "root_of_unity requires a power-of-two order");
+        let s = n.trailing_zeros();
+        assert!(s <= TWO_ADICITY, "field has no subgroup of order {}", n);
+
+        // A primitive `2^TWO_ADICITY`-th root of unity, squared down to a
+        // primitive `n`-th root.
+        let mut root = FieldElement::new(generator()).pow(&((modulus() - BigInt::one()) >> TWO_ADICITY));
+        for _ in 0..(TWO_ADICITY - s) {
+            root = FieldElement::mul(&root, &root);
+        }
+        root
+    }
+}
+
+impl PartialEq for FieldElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+impl Eq for FieldElement {}
+
+impl Add for &FieldElement {
+    type Output = FieldElement;
+    fn add(self, other: &FieldElement) -> FieldElement {
+        FieldElement::add(self, other)
+    }
+}
+
+impl Add for FieldElement {
+    type Output = FieldElement;
+    fn add(self, other: FieldElement) -> FieldElement {
+        FieldElement::add(&self, &other)
+    }
+}
+
+impl AddAssign for FieldElement {
+    fn add_assign(&mut self, other: FieldElement) {
+        *self = FieldElement::add(self, &other);
+    }
+}
+
+impl Sub for &FieldElement {
+    type Output = FieldElement;
+    fn sub(self, other: &FieldElement) -> FieldElement {
+        FieldElement::sub(self, other)
+    }
+}
+
+impl Mul for &FieldElement {
+    type Output = FieldElement;
+    fn mul(self, other: &FieldElement) -> FieldElement {
+        FieldElement::mul(self, other)
+    }
+}
+
+impl Mul<&BigInt> for FieldElement {
+    type Output = FieldElement;
+    fn mul(self, other: &BigInt) -> FieldElement {
+        FieldElement::new(&self.value * other)
+    }
+}
@@ -0,0 +1,166 @@
+use crate::field::FieldElement;
+use crate::r1cs::ConstraintSystem;
+use num_bigint::BigInt;
+use num_traits::One;
+
+/// A gadget layer over `ConstraintSystem`, modeled on bellman's
+/// `boolean`/`uint32` gadgets: where `Circuit`'s `Add`/`Mul` gates can only
+/// wire together two single-term constraints, these build on the corrected
+/// multi-term R1CS to express booleanity, bit decomposition, bitwise
+/// operations, and range comparisons.
+///
+/// Every gadget here except `and` takes the index of a constant-`1` wire
+/// (`one`, allocated once per `ConstraintSystem` the way
+/// `main::merkle_tree_proof` does for `poseidon_gadget`). `alloc_bit`,
+/// `and`, `or`, `xor`, and `not` return the index of their result bit and
+/// `bit_decompose` returns the indices of its bits; the `a·b` product that
+/// `or`/`xor` allocate and the shifted value and bits that
+/// `enforce_less_than` allocates are not returned.
+
+/// Allocates a variable and constrains it to be boolean via `b·(1−b) = 0`.
+/// `value` must already be `0` or `1`; the constraint is what makes a
+/// dishonest prover's non-boolean witness unsatisfiable, not a check here.
+pub fn alloc_bit(cs: &mut ConstraintSystem, one: usize, value: FieldElement) -> usize {
+    let bit = cs.alloc(value);
+    cs.enforce(
+        &[(bit, FieldElement::one())],
+        &[(one, FieldElement::one()), (bit, FieldElement::one().neg())],
+        &[],
+    );
+    bit
+}
+
+/// Allocates `n` bits `b_0..b_{n-1}` (LSB first) and constrains
+/// `sum(b_i * 2^i) = var`, where `var` is `(index, value)` of an
+/// already-allocated variable.
+pub fn bit_decompose(cs: &mut ConstraintSystem, one: usize, var: (usize, FieldElement), n: usize) -> Vec<usize> {
+    let mut remaining = var.1.get_value();
+    let mut bits = Vec::with_capacity(n);
+    let mut sum_terms = Vec::with_capacity(n);
+    let mut power = BigInt::one();
+
+    for _ in 0..n {
+        let bit_value = &remaining % 2;
+        remaining = (&remaining - &bit_value) / 2;
+
+        let bit_index = alloc_bit(cs, one, FieldElement::new(bit_value));
+        sum_terms.push((bit_index, FieldElement::new(power.clone())));
+        bits.push(bit_index);
+        power = power * 2;
+    }
+
+    cs.enforce(&sum_terms, &[(one, FieldElement::one())], &[(var.0, FieldElement::one())]);
+    bits
+}
+
+/// Boolean AND of two allocated bits: `c = a · b`, already boolean since
+/// the product of two bits is a bit.
+pub fn and(cs: &mut ConstraintSystem, a: (usize, FieldElement), b: (usize, FieldElement)) -> usize {
+    let c_value = a.1.mul(&b.1);
+    let c = cs.alloc(c_value);
+    cs.enforce(&[(a.0, FieldElement::one())], &[(b.0, FieldElement::one())], &[(c, FieldElement::one())]);
+    c
+}
+
+/// Boolean OR of two allocated bits: `c = a + b − a·b`.
+pub fn or(cs: &mut ConstraintSystem, one: usize, a: (usize, FieldElement), b: (usize, FieldElement)) -> usize {
+    let ab = a.1.mul(&b.1);
+    let ab_index = cs.alloc(ab.clone());
+    cs.enforce(&[(a.0, FieldElement::one())], &[(b.0, FieldElement::one())], &[(ab_index, FieldElement::one())]);
+
+    let c_value = a.1.add(&b.1).sub(&ab);
+    let c = cs.alloc(c_value);
+    cs.enforce(
+        &[(a.0, FieldElement::one()), (b.0, FieldElement::one()), (ab_index, FieldElement::one().neg())],
+        &[(one, FieldElement::one())],
+        &[(c, FieldElement::one())],
+    );
+    c
+}
+
+/// Boolean XOR of two allocated bits: `c = a + b − 2·a·b`.
+pub fn xor(cs: &mut ConstraintSystem, one: usize, a: (usize, FieldElement), b: (usize, FieldElement)) -> usize {
+    let ab = a.1.mul(&b.1);
+    let ab_index = cs.alloc(ab.clone());
+    cs.enforce(&[(a.0, FieldElement::one())], &[(b.0, FieldElement::one())], &[(ab_index, FieldElement::one())]);
+
+    let two_ab = ab.add(&ab);
+    let c_value = a.1.add(&b.1).sub(&two_ab);
+    let c = cs.alloc(c_value);
+    cs.enforce(
+        &[
+            (a.0, FieldElement::one()),
+            (b.0, FieldElement::one()),
+            (ab_index, FieldElement::new(BigInt::from(-2))),
+        ],
+        &[(one, FieldElement::one())],
+        &[(c, FieldElement::one())],
+    );
+    c
+}
+
+/// Boolean NOT of an allocated bit: `c = 1 − a`.
+pub fn not(cs: &mut ConstraintSystem, one: usize, a: (usize, FieldElement)) -> usize {
+    let c_value = FieldElement::one().sub(&a.1);
+    let c = cs.alloc(c_value);
+    cs.enforce(
+        &[(one, FieldElement::one()), (a.0, FieldElement::one().neg())],
+        &[(one, FieldElement::one())],
+        &[(c, FieldElement::one())],
+    );
+    c
+}
+
+/// Enforces `a < b`, treating both as `n`-bit values: decomposing
+/// `shifted = b − a − 1 + 2^n` into `n + 1` bits pins its top bit to `1`
+/// exactly when `b − a − 1` is a non-negative value representable in `n`
+/// bits, which is what makes the comparison unsatisfiable for a dishonest
+/// witness where `a >= b`. Subtracting the extra `1` (rather than shifting
+/// by `2^n` alone) is what excludes `a == b`, for which `b − a + 2^n` would
+/// be exactly `2^n` and still decompose with its top bit set.
+pub fn enforce_less_than(cs: &mut ConstraintSystem, one: usize, a: (usize, FieldElement), b: (usize, FieldElement), n: usize) {
+    let offset = FieldElement::new(BigInt::from(2u64).pow(n as u32) - BigInt::one());
+    let shifted_value = offset.add(&b.1).sub(&a.1);
+    let shifted = cs.alloc(shifted_value.clone());
+    cs.enforce(
+        &[(b.0, FieldElement::one()), (a.0, FieldElement::one().neg()), (one, offset)],
+        &[(one, FieldElement::one())],
+        &[(shifted, FieldElement::one())],
+    );
+
+    let bits = bit_decompose(cs, one, (shifted, shifted_value), n + 1);
+    let top_bit = *bits.last().unwrap();
+    cs.enforce(&[(top_bit, FieldElement::one())], &[(one, FieldElement::one())], &[(one, FieldElement::one())]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::ToBigInt;
+
+    #[test]
+    fn enforce_less_than_accepts_a_strictly_smaller_than_b() {
+        let mut cs = ConstraintSystem::new();
+        let one = cs.alloc(FieldElement::one());
+        let a = cs.alloc(FieldElement::new(3.to_bigint().unwrap()));
+        let b = cs.alloc(FieldElement::new(5.to_bigint().unwrap()));
+
+        enforce_less_than(&mut cs, one, (a, FieldElement::new(3.to_bigint().unwrap())), (b, FieldElement::new(5.to_bigint().unwrap())), 4);
+
+        let r1cs = cs.into_r1cs();
+        assert!(r1cs.verify_witness(&r1cs.generate_witness()).is_ok());
+    }
+
+    #[test]
+    fn enforce_less_than_rejects_a_equal_to_b() {
+        let mut cs = ConstraintSystem::new();
+        let one = cs.alloc(FieldElement::one());
+        let a = cs.alloc(FieldElement::new(5.to_bigint().unwrap()));
+        let b = cs.alloc(FieldElement::new(5.to_bigint().unwrap()));
+
+        enforce_less_than(&mut cs, one, (a, FieldElement::new(5.to_bigint().unwrap())), (b, FieldElement::new(5.to_bigint().unwrap())), 4);
+
+        let r1cs = cs.into_r1cs();
+        assert!(r1cs.verify_witness(&r1cs.generate_witness()).is_err());
+    }
+}
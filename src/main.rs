@@ -4,13 +4,20 @@ mod merkle;
 mod qap;
 mod field;
 mod proof;
+mod pairing;
+mod circom;
+mod poseidon;
+mod sparse_merkle;
+mod error;
+mod gadgets;
 
 use num_bigint::{ToBigInt};
 use circuit::Circuit;
 use crate::field::FieldElement;
+use crate::r1cs::ConstraintSystem;
 
 /// A simple addition proof using the Circuit
-fn addition_proof() {
+fn addition_proof() -> Result<(), error::ZkError> {
     let mut circuit = Circuit::new();
 
     let input1 = circuit.add_input(FieldElement::new(10.to_bigint().unwrap()));
@@ -27,13 +34,19 @@ fn addition_proof() {
 
     // Generate and verify the addition proof
     println!("Generating Addition Proof...");
-    circuit.generate_proof("addition_proof.bin");
-    let is_valid = circuit.verify_proof("addition_proof.bin");
+    circuit.generate_proof("addition_proof.bin")?;
+    let is_valid = circuit.verify_proof("addition_proof.bin")?;
     println!("Addition Proof is valid: {}", is_valid);
+    Ok(())
 }
 
-/// A Merkle Tree proof demonstrating the use of a Merkle path in a zk-circuit
-fn merkle_tree_proof() {
+/// A Merkle Tree proof demonstrating in-circuit Poseidon path verification.
+///
+/// `Circuit`'s `Add`/`Mul` gates can't express a hash, so this builds the
+/// R1CS directly with `ConstraintSystem`, binding each level of the path
+/// through `poseidon::poseidon_gadget` and asserting the final computed
+/// root matches the tree's actual root.
+fn merkle_tree_proof() -> Result<(), error::ZkError> {
     let transactions = vec![
         10.to_bigint().unwrap(),
         20.to_bigint().unwrap(),
@@ -41,57 +54,57 @@ fn merkle_tree_proof() {
         80.to_bigint().unwrap(),
     ];
 
-    // Create the MerkleTree
     let merkle_tree = merkle::MerkleTree::new(transactions.clone());
     let leaf_index = 2;
     let leaf_value = transactions[leaf_index].clone();
     let merkle_path = merkle_tree.merkle_path(leaf_index);
 
-    let mut circuit = Circuit::new();  // Use modulus for Merkle proofs
+    let mut cs = ConstraintSystem::new();
+    let one = cs.alloc(FieldElement::one());
 
-    let leaf_index_var = circuit.add_input(FieldElement::new(leaf_value));
-    let mut current_hash_index = leaf_index_var;
+    let mut current_value = FieldElement::new(leaf_value);
+    let mut current_index = cs.alloc(current_value.clone());
 
     for (sibling_hash, is_left) in merkle_path {
-        let sibling_index_var = circuit.add_input(FieldElement::new(sibling_hash.clone()));
-
-        // Compute the new hash based on the sibling relationship
-        let new_hash_value = if is_left {
-            merkle_tree.apply_hash(
-                circuit.get_input(sibling_index_var).expect("Invalid input index"),
-                circuit.get_input(current_hash_index).expect("Invalid input index"),
-            )
-        } else {
-            merkle_tree.apply_hash(
-                circuit.get_input(current_hash_index).expect("Invalid input index"),
-                circuit.get_input(sibling_index_var).expect("Invalid input index"),
-            )
-        };
+        let sibling_value = FieldElement::new(sibling_hash);
+        let sibling_index = cs.alloc(sibling_value.clone());
 
-        let new_hash_index = circuit.add_input(new_hash_value.clone());
-        circuit.set_output(new_hash_value.clone());
-
-        // Add a hash gate with correct sibling ordering for Merkle path
-        circuit.add_gate(if is_left {
-            circuit::Gate::Add(sibling_index_var, current_hash_index, new_hash_index)
+        let (new_index, new_value) = if is_left {
+            poseidon::poseidon_gadget(&mut cs, one, (sibling_index, sibling_value), (current_index, current_value))
         } else {
-            circuit::Gate::Add(current_hash_index, sibling_index_var, new_hash_index)
-        });
+            poseidon::poseidon_gadget(&mut cs, one, (current_index, current_value), (sibling_index, sibling_value))
+        };
 
-        current_hash_index = new_hash_index;
+        current_index = new_index;
+        current_value = new_value;
     }
 
-    // Set the final computed root in the circuit
-    circuit.set_output(FieldElement::new(merkle_tree.root.clone()));
+    // Pin the computed root to the tree's actual root: a constraint that
+    // only a witness matching the real Merkle path can satisfy.
+    let expected_root = FieldElement::new(merkle_tree.root.clone());
+    cs.enforce(
+        &[(current_index, FieldElement::one())],
+        &[(one, FieldElement::one())],
+        &[(one, expected_root.clone())],
+    );
 
     println!("Expected Merkle root: {}", merkle_tree.root);
-    circuit.generate_proof("merkle_proof.bin");
-    let is_valid = circuit.verify_proof("merkle_proof.bin");
+
+    let r1cs = cs.into_r1cs();
+    let witness = r1cs.generate_witness();
+    r1cs.verify_witness(&witness)?;
+
+    let backend = pairing::InsecureDemoBackend::acknowledge_no_cryptographic_hardness();
+    let (proving_key, verifying_key) = proof::setup(&backend, &r1cs);
+    let groth_proof = proof::prove(&backend, &proving_key, &r1cs, &witness)?;
+
+    let is_valid = proof::verify(&backend, &verifying_key, &[], &groth_proof);
     println!("Merkle Tree Proof is valid: {}", is_valid);
+    Ok(())
 }
 
 /// A function to demonstrate a multiplication proof using the Circuit and R1CS components
-fn multiplication_proof() {
+fn multiplication_proof() -> Result<(), error::ZkError> {
     let mut circuit = Circuit::new();  // Using modulus for demonstration
 
     let input1 = circuit.add_input(FieldElement::new(3.to_bigint().unwrap())); // `a`
@@ -107,14 +120,16 @@ fn multiplication_proof() {
 
     // Generate and verify the multiplication proof
     println!("Generating Multiplication Proof...");
-    circuit.generate_proof("multiplication_proof.bin");
-    let is_valid = circuit.verify_proof("multiplication_proof.bin");
+    circuit.generate_proof("multiplication_proof.bin")?;
+    let is_valid = circuit.verify_proof("multiplication_proof.bin")?;
     println!("Multiplication Proof is valid: {}", is_valid);
+    Ok(())
 }
 
-fn main() {
+fn main() -> Result<(), error::ZkError> {
     // Run each proof demonstration
-    addition_proof();
-    multiplication_proof();
-    merkle_tree_proof(); // Include the merkle_tree_proof function
+    addition_proof()?;
+    multiplication_proof()?;
+    merkle_tree_proof()?; // Include the merkle_tree_proof function
+    Ok(())
 }
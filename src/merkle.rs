@@ -0,0 +1,58 @@
+use crate::field::FieldElement;
+use crate::poseidon;
+use num_bigint::BigInt;
+
+/// A binary Merkle tree over `BigInt` leaves, hashed with Poseidon so that
+/// path verification can be expressed as a handful of R1CS constraints per
+/// level (see `poseidon::poseidon_gadget`) instead of a circuit-unfriendly
+/// bit-oriented hash.
+pub struct MerkleTree {
+    pub root: BigInt,
+    layers: Vec<Vec<BigInt>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree bottom-up from `leaves`. An odd layer duplicates its
+    /// last element before pairing, the common convention for non-power-of
+    /// -two leaf counts.
+    pub fn new(leaves: Vec<BigInt>) -> Self {
+        assert!(!leaves.is_empty(), "MerkleTree needs at least one leaf");
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let current = layers.last().unwrap();
+            let mut next = Vec::with_capacity((current.len() + 1) / 2);
+            for pair in current.chunks(2) {
+                let left = FieldElement::new(pair[0].clone());
+                let right = FieldElement::new(pair.get(1).unwrap_or(&pair[0]).clone());
+                next.push(poseidon::poseidon_hash(&[left, right]).get_value());
+            }
+            layers.push(next);
+        }
+
+        let root = layers.last().unwrap()[0].clone();
+        MerkleTree { root, layers }
+    }
+
+    /// The Poseidon 2-to-1 hash used to combine a `(left, right)` pair at
+    /// every level of the tree.
+    pub fn apply_hash(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        poseidon::poseidon_hash(&[left.clone(), right.clone()])
+    }
+
+    /// Returns the sibling hash and a `is_left` flag (true if the sibling
+    /// belongs on the left of the pairing) for every level on the path
+    /// from `leaf_index` up to the root.
+    pub fn merkle_path(&self, leaf_index: usize) -> Vec<(BigInt, bool)> {
+        let mut path = Vec::new();
+        let mut index = leaf_index;
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let sibling_index = if index % 2 == 0 { index + 1 } else { index - 1 };
+            let sibling = layer.get(sibling_index).unwrap_or(&layer[index]).clone();
+            let is_left = index % 2 == 1;
+            path.push((sibling, is_left));
+            index /= 2;
+        }
+        path
+    }
+}
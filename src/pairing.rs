@@ -0,0 +1,142 @@
+use crate::field::FieldElement;
+use serde::{Deserialize, Serialize};
+
+/// An explicit acknowledgment that this module's "Groth16" backend has no
+/// cryptographic hardness behind it, required by every `proof::setup`/
+/// `proof::prove`/`proof::verify` call so that using the insecure backend
+/// can't happen by accident.
+///
+/// `G1Affine`/`G2Affine`/`pairing` track a point by its discrete log
+/// against the implicit generator instead of implementing the full short
+/// Weierstrass curve, so group addition, scalar multiplication, and the
+/// pairing stay algebraically faithful to a Groth16-*shaped* verification
+/// equation without a full elliptic-curve arithmetic stack. That
+/// "exponent" is not a cryptographic hardness assumption, it's a
+/// relabeled `FieldElement`: it's stored and serialized in the clear, so
+/// this module provides no hiding and no soundness. Concretely, every
+/// scalar in a `VerifyingKey` is readable by anyone who can read the
+/// bytes of `vk_file.bin`, and the Groth16 verification equation is
+/// linear in those scalars, so an attacker can read
+/// `alpha`/`beta`/`gamma`/`delta`/`ic` straight out of a serialized
+/// `VerifyingKey` and solve for a `Proof` that `proof::verify` accepts
+/// for any public input, without ever holding a witness. This backend is
+/// only good for exercising the Groth16 *shape* (setup/prove/verify, SRS
+/// structure, constant proof size) end to end, and must never be reached
+/// for anything where soundness or hiding is load-bearing. A real fix
+/// needs an actual pairing-friendly curve (point arithmetic over
+/// `Fp`/`Fp2` plus a Miller-loop pairing), which is a substantial
+/// addition this crate doesn't have yet.
+#[derive(Clone, Copy, Debug)]
+pub struct InsecureDemoBackend;
+
+impl InsecureDemoBackend {
+    /// The only constructor, named so every call site reads as what it
+    /// is: an explicit acknowledgment that `pairing`'s scalars are
+    /// plaintext and the resulting "proofs" are forgeable, not a real
+    /// SNARK backend. See the [`InsecureDemoBackend`] docs before
+    /// reaching for this anywhere the properties Groth16 is supposed to
+    /// provide actually matter.
+    pub fn acknowledge_no_cryptographic_hardness() -> Self {
+        InsecureDemoBackend
+    }
+}
+
+/// A minimal stand-in for a BN254 `G1` affine point. See
+/// [`InsecureDemoBackend`] for why this is not safe to use as a real
+/// SNARK backend.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct G1Affine {
+    exponent: FieldElement,
+}
+
+/// The `G2` analogue of `G1Affine`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct G2Affine {
+    exponent: FieldElement,
+}
+
+/// An element of the target group `GT`. `GT` is written multiplicatively
+/// in the pairing equation, which corresponds to adding exponents here.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GtElement {
+    exponent: FieldElement,
+}
+
+impl G1Affine {
+    pub fn identity() -> Self {
+        G1Affine { exponent: FieldElement::zero() }
+    }
+
+    pub fn generator() -> Self {
+        G1Affine { exponent: FieldElement::one() }
+    }
+
+    pub fn from_scalar(scalar: &FieldElement) -> Self {
+        G1Affine { exponent: scalar.clone() }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        G1Affine { exponent: self.exponent.add(&other.exponent) }
+    }
+
+    pub fn scalar_mul(&self, scalar: &FieldElement) -> Self {
+        G1Affine { exponent: self.exponent.mul(scalar) }
+    }
+}
+
+impl G2Affine {
+    pub fn identity() -> Self {
+        G2Affine { exponent: FieldElement::zero() }
+    }
+
+    pub fn generator() -> Self {
+        G2Affine { exponent: FieldElement::one() }
+    }
+
+    pub fn from_scalar(scalar: &FieldElement) -> Self {
+        G2Affine { exponent: scalar.clone() }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        G2Affine { exponent: self.exponent.add(&other.exponent) }
+    }
+
+    pub fn scalar_mul(&self, scalar: &FieldElement) -> Self {
+        G2Affine { exponent: self.exponent.mul(scalar) }
+    }
+}
+
+impl GtElement {
+    pub fn identity() -> Self {
+        GtElement { exponent: FieldElement::zero() }
+    }
+
+    /// Group multiplication in `GT`.
+    pub fn mul(&self, other: &Self) -> Self {
+        GtElement { exponent: self.exponent.add(&other.exponent) }
+    }
+}
+
+/// Multi-scalar multiplication: `sum_i scalars[i] * bases[i]`.
+pub fn multiexp_g1(bases: &[G1Affine], scalars: &[FieldElement]) -> G1Affine {
+    assert_eq!(bases.len(), scalars.len(), "multiexp: bases/scalars length mismatch");
+    bases
+        .iter()
+        .zip(scalars)
+        .fold(G1Affine::identity(), |acc, (base, scalar)| acc.add(&base.scalar_mul(scalar)))
+}
+
+/// `G2` analogue of [`multiexp_g1`].
+pub fn multiexp_g2(bases: &[G2Affine], scalars: &[FieldElement]) -> G2Affine {
+    assert_eq!(bases.len(), scalars.len(), "multiexp: bases/scalars length mismatch");
+    bases
+        .iter()
+        .zip(scalars)
+        .fold(G2Affine::identity(), |acc, (base, scalar)| acc.add(&base.scalar_mul(scalar)))
+}
+
+/// The bilinear map `e: G1 x G2 -> GT`. Bilinearity is immediate from the
+/// discrete-log tracking above: `e(aP, bQ) = e(P, Q)^(ab)`.
+pub fn pairing(a: &G1Affine, b: &G2Affine) -> GtElement {
+    GtElement { exponent: a.exponent.mul(&b.exponent) }
+}
@@ -0,0 +1,188 @@
+use crate::field::FieldElement;
+use crate::r1cs::ConstraintSystem;
+use num_bigint::BigInt;
+
+/// Sponge width for a 2-to-1 compression: `[capacity, left, right]`.
+const T: usize = 3;
+/// S-box exponent. BN254's `Fr` has `gcd(5, p-1) = 1`, so `x -> x^5` is a
+/// permutation of the field.
+const ALPHA: u64 = 5;
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 56;
+
+/// A sparse linear combination of allocated witness variables, as used by
+/// `ConstraintSystem::enforce`.
+type Lc = Vec<(usize, FieldElement)>;
+
+/// Deterministically derives this Poseidon instance's round constants and
+/// MDS matrix from a small seed, rather than importing a published
+/// parameter set. Adequate for this crate's demonstration purposes, not a
+/// substitute for vetted constants in a production deployment.
+struct Poseidon {
+    round_constants: Vec<Vec<FieldElement>>, // [round][lane]
+    mds: Vec<Vec<FieldElement>>,             // [T][T]
+}
+
+impl Poseidon {
+    fn new() -> Self {
+        Poseidon { round_constants: Self::round_constants(), mds: Self::mds_matrix() }
+    }
+
+    fn round_constants() -> Vec<Vec<FieldElement>> {
+        let total_rounds = FULL_ROUNDS + PARTIAL_ROUNDS;
+        let mut constants = Vec::with_capacity(total_rounds);
+        let mut state = FieldElement::one();
+        for _ in 0..total_rounds {
+            let mut round = Vec::with_capacity(T);
+            for _ in 0..T {
+                state = state.mul(&state).add(&FieldElement::one());
+                round.push(state.clone());
+            }
+            constants.push(round);
+        }
+        constants
+    }
+
+    /// A `T x T` Cauchy matrix `M[i][j] = 1 / (x_i + y_j)` for distinct
+    /// `x_i`, `y_j`. Cauchy matrices are always invertible, which is the
+    /// property an MDS matrix needs.
+    fn mds_matrix() -> Vec<Vec<FieldElement>> {
+        let xs: Vec<FieldElement> = (0..T as u64).map(|i| FieldElement::new(BigInt::from(i))).collect();
+        let ys: Vec<FieldElement> = (0..T as u64).map(|i| FieldElement::new(BigInt::from(T as u64 + i))).collect();
+        xs.iter()
+            .map(|x| ys.iter().map(|y| x.add(y).inverse()).collect())
+            .collect()
+    }
+
+    fn sbox(x: &FieldElement) -> FieldElement {
+        x.pow(&BigInt::from(ALPHA))
+    }
+
+    fn apply_mds(&self, state: &[FieldElement]) -> Vec<FieldElement> {
+        (0..T)
+            .map(|i| (0..T).fold(FieldElement::zero(), |acc, j| acc.add(&self.mds[i][j].mul(&state[j]))))
+            .collect()
+    }
+
+    /// Runs the full Poseidon permutation (`R_F` full rounds split around
+    /// `R_P` partial rounds) over `state`, which must have length `T`.
+    fn permute(&self, state: &[FieldElement]) -> Vec<FieldElement> {
+        let mut state = state.to_vec();
+        let half_full = FULL_ROUNDS / 2;
+        for round in 0..(FULL_ROUNDS + PARTIAL_ROUNDS) {
+            for (lane, constant) in state.iter_mut().zip(&self.round_constants[round]) {
+                *lane = lane.add(constant);
+            }
+
+            let is_full_round = round < half_full || round >= half_full + PARTIAL_ROUNDS;
+            if is_full_round {
+                for lane in state.iter_mut() {
+                    *lane = Self::sbox(lane);
+                }
+            } else {
+                state[0] = Self::sbox(&state[0]);
+            }
+
+            state = self.apply_mds(&state);
+        }
+        state
+    }
+}
+
+/// Hashes `inputs` (length `T - 1`) with the Poseidon sponge: the state is
+/// initialized as `[0, inputs...]`, permuted, and lane 0 is the digest.
+pub fn poseidon_hash(inputs: &[FieldElement]) -> FieldElement {
+    assert_eq!(inputs.len(), T - 1, "poseidon_hash expects {} inputs for width {}", T - 1, T);
+    let mut state = vec![FieldElement::zero()];
+    state.extend_from_slice(inputs);
+    Poseidon::new().permute(&state)[0].clone()
+}
+
+fn lc_scale(lc: &Lc, scalar: &FieldElement) -> Lc {
+    lc.iter().map(|(index, coeff)| (*index, coeff.mul(scalar))).collect()
+}
+
+fn lc_add(a: &Lc, b: &Lc) -> Lc {
+    let mut merged = a.to_vec();
+    for (index, coeff) in b {
+        if let Some(existing) = merged.iter_mut().find(|(i, _)| i == index) {
+            existing.1 = existing.1.add(coeff);
+        } else {
+            merged.push((*index, coeff.clone()));
+        }
+    }
+    merged
+}
+
+/// Emits the constraints for one `x -> x^5` S-box application: pins the
+/// (possibly multi-term) affine input `lc` into a fresh variable, then
+/// chains three multiplications (`x^2`, `x^4`, `x^5`). Returns the new
+/// lane as a single-variable linear combination, along with its value.
+fn enforce_sbox(cs: &mut ConstraintSystem, one_index: usize, lc: &Lc, value: &FieldElement) -> (Lc, FieldElement) {
+    let x_index = cs.alloc(value.clone());
+    cs.enforce(lc, &[(one_index, FieldElement::one())], &[(x_index, FieldElement::one())]);
+
+    let x2_value = value.mul(value);
+    let x2_index = cs.alloc(x2_value.clone());
+    cs.enforce(&[(x_index, FieldElement::one())], &[(x_index, FieldElement::one())], &[(x2_index, FieldElement::one())]);
+
+    let x4_value = x2_value.mul(&x2_value);
+    let x4_index = cs.alloc(x4_value.clone());
+    cs.enforce(&[(x2_index, FieldElement::one())], &[(x2_index, FieldElement::one())], &[(x4_index, FieldElement::one())]);
+
+    let x5_value = x4_value.mul(value);
+    let x5_index = cs.alloc(x5_value.clone());
+    cs.enforce(&[(x4_index, FieldElement::one())], &[(x_index, FieldElement::one())], &[(x5_index, FieldElement::one())]);
+
+    (vec![(x5_index, FieldElement::one())], x5_value)
+}
+
+/// Constraint-emitting 2-to-1 Poseidon compression: given allocated `left`
+/// and `right` variables (index and value), binds every round's S-box
+/// applications into the constraint system and returns the allocated
+/// output variable. Round-constant addition and MDS mixing are affine, so
+/// they're folded directly into the linear combinations fed to each
+/// S-box instead of costing constraints of their own.
+pub fn poseidon_gadget(
+    cs: &mut ConstraintSystem,
+    one_index: usize,
+    left: (usize, FieldElement),
+    right: (usize, FieldElement),
+) -> (usize, FieldElement) {
+    let poseidon = Poseidon::new();
+
+    let mut lcs: Vec<Lc> = vec![Vec::new(), vec![(left.0, FieldElement::one())], vec![(right.0, FieldElement::one())]];
+    let mut values: Vec<FieldElement> = vec![FieldElement::zero(), left.1, right.1];
+
+    let half_full = FULL_ROUNDS / 2;
+    for round in 0..(FULL_ROUNDS + PARTIAL_ROUNDS) {
+        for lane in 0..T {
+            let constant = &poseidon.round_constants[round][lane];
+            lcs[lane] = lc_add(&lcs[lane], &vec![(one_index, constant.clone())]);
+            values[lane] = values[lane].add(constant);
+        }
+
+        let is_full_round = round < half_full || round >= half_full + PARTIAL_ROUNDS;
+        let sbox_lanes: Vec<usize> = if is_full_round { (0..T).collect() } else { vec![0] };
+        for lane in sbox_lanes {
+            let (new_lc, new_value) = enforce_sbox(cs, one_index, &lcs[lane], &values[lane]);
+            lcs[lane] = new_lc;
+            values[lane] = new_value;
+        }
+
+        let mixed_lcs: Vec<Lc> = (0..T)
+            .map(|i| (0..T).fold(Vec::new(), |acc, j| lc_add(&acc, &lc_scale(&lcs[j], &poseidon.mds[i][j]))))
+            .collect();
+        let mixed_values: Vec<FieldElement> = (0..T)
+            .map(|i| (0..T).fold(FieldElement::zero(), |acc, j| acc.add(&poseidon.mds[i][j].mul(&values[j]))))
+            .collect();
+        lcs = mixed_lcs;
+        values = mixed_values;
+    }
+
+    let root_value = values[0].clone();
+    let root_index = cs.alloc(root_value.clone());
+    cs.enforce(&lcs[0], &[(one_index, FieldElement::one())], &[(root_index, FieldElement::one())]);
+
+    (root_index, root_value)
+}
@@ -0,0 +1,195 @@
+use crate::error::ZkError;
+use crate::field::FieldElement;
+use crate::pairing::{self, G1Affine, G2Affine, GtElement, InsecureDemoBackend};
+use crate::r1cs::R1CS;
+use num_bigint::BigInt;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Write;
+
+/// The structured reference string half that only the prover needs: one
+/// group element per witness variable, plus the quotient-polynomial query
+/// vector, all evaluated at the (discarded) toxic-waste sample `tau`.
+#[derive(Serialize, Deserialize)]
+pub struct ProvingKey {
+    alpha_g1: G1Affine,
+    beta_g2: G2Affine,
+    /// Number of leading `R1CS` variables treated as public (see
+    /// `R1CS::num_public_inputs`); `witness[..num_public]` is folded into
+    /// `VerifyingKey::ic` instead of `c_query`.
+    num_public: usize,
+    a_query: Vec<G1Affine>,    // A_i(tau) * G1, one per variable
+    b_query_g2: Vec<G2Affine>, // B_i(tau) * G2, one per variable
+    c_query: Vec<G1Affine>,    // (beta*A_i + alpha*B_i + C_i)(tau) / delta * G1, one per private variable
+    h_query: Vec<G1Affine>,    // tau^j * Z(tau) / delta * G1, one per coefficient of h(x)
+}
+
+/// The structured reference string half the verifier needs.
+#[derive(Serialize, Deserialize)]
+pub struct VerifyingKey {
+    alpha_g1: G1Affine,
+    beta_g2: G2Affine,
+    gamma_g2: G2Affine,
+    delta_g2: G2Affine,
+    /// `ic[0]` is a constant term; `ic[1..]` has one entry per public
+    /// input, in `R1CS` variable order.
+    ic: Vec<G1Affine>,
+}
+
+/// A Groth16-*shaped* proof: three constant-size group elements,
+/// independent of the number of constraints in the circuit.
+///
+/// Succinct, but — per `pairing::InsecureDemoBackend`'s doc comment —
+/// neither hiding nor sound: `pairing`'s group elements are bare scalars
+/// in disguise, so `a`/`b`/`c` here are directly solvable linear
+/// combinations of the witness (or, given only a `VerifyingKey`, directly
+/// forgeable), not curve points a verifier is computationally unable to
+/// invert.
+#[derive(Serialize, Deserialize)]
+pub struct Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+/// Samples the toxic waste and derives a `(ProvingKey, VerifyingKey)` pair
+/// from `r1cs`'s QAP. The sampled values are used only to build the query
+/// vectors below and are dropped at the end of this function, as in a real
+/// trusted setup — though with `pairing`'s current stand-in backend,
+/// "toxic" is aspirational: the query vectors it produces are linear in
+/// those samples, not hidden behind a hard problem. `_backend` is an
+/// [`InsecureDemoBackend`] token, required so this can't be called
+/// without the caller having acknowledged that.
+pub fn setup(_backend: &InsecureDemoBackend, r1cs: &R1CS) -> (ProvingKey, VerifyingKey) {
+    let mut rng = rand::thread_rng();
+    let sample = |rng: &mut rand::rngs::ThreadRng| FieldElement::new(BigInt::from(rng.gen::<u64>()));
+
+    let tau = sample(&mut rng);
+    let alpha = sample(&mut rng);
+    let beta = sample(&mut rng);
+    let gamma = sample(&mut rng);
+    let delta = sample(&mut rng);
+
+    let num_vars = r1cs.variables.len();
+    let num_public = r1cs.num_public_inputs.min(num_vars);
+    let (a_evals, b_evals, c_evals) = r1cs.qap.variable_evaluations(num_vars, &tau);
+
+    let domain = r1cs.qap.domain();
+    let z_at_tau = domain.evaluate_vanishing(&tau);
+    let delta_inv = delta.inverse();
+    let gamma_inv = gamma.inverse();
+
+    // A and B range over every variable, public and private alike.
+    let a_query: Vec<G1Affine> = a_evals.iter().map(G1Affine::from_scalar).collect();
+    let b_query_g2: Vec<G2Affine> = b_evals.iter().map(G2Affine::from_scalar).collect();
+
+    let combined_at = |i: usize| beta.mul(&a_evals[i]).add(&alpha.mul(&b_evals[i])).add(&c_evals[i]);
+
+    // Public variables' combination is folded into `vk.ic` via gamma, so
+    // the verifier can recompute it from values it already knows; private
+    // variables' combination stays inside the proof's `C` via delta.
+    let mut ic = vec![G1Affine::identity()];
+    ic.extend((0..num_public).map(|i| G1Affine::from_scalar(&combined_at(i).mul(&gamma_inv))));
+
+    let c_query: Vec<G1Affine> = (num_public..num_vars)
+        .map(|i| G1Affine::from_scalar(&combined_at(i).mul(&delta_inv)))
+        .collect();
+
+    let mut h_query = Vec::with_capacity(domain.m);
+    let mut tau_power = FieldElement::one();
+    for _ in 0..domain.m {
+        h_query.push(G1Affine::from_scalar(&tau_power.mul(&z_at_tau).mul(&delta_inv)));
+        tau_power = tau_power.mul(&tau);
+    }
+
+    let pk = ProvingKey {
+        alpha_g1: G1Affine::from_scalar(&alpha),
+        beta_g2: G2Affine::from_scalar(&beta),
+        num_public,
+        a_query,
+        b_query_g2,
+        c_query,
+        h_query,
+    };
+
+    let vk = VerifyingKey {
+        alpha_g1: G1Affine::from_scalar(&alpha),
+        beta_g2: G2Affine::from_scalar(&beta),
+        gamma_g2: G2Affine::from_scalar(&gamma),
+        delta_g2: G2Affine::from_scalar(&delta),
+        ic,
+    };
+
+    (pk, vk)
+}
+
+/// Multiexponentiates `witness` against `pk`'s query vectors to build the
+/// three constant-size proof elements. Returns `Err` (instead of the old
+/// panic) if `witness` doesn't actually satisfy `r1cs`: callers that
+/// haven't already validated the witness with `R1CS::verify_witness`
+/// would otherwise hit `QAP::quotient`'s division-by-the-vanishing-
+/// polynomial failure.
+pub fn prove(
+    _backend: &InsecureDemoBackend,
+    pk: &ProvingKey,
+    r1cs: &R1CS,
+    witness: &[FieldElement],
+) -> Result<Proof, ZkError> {
+    let h = r1cs.qap.quotient(witness)?;
+
+    let a = pk.alpha_g1.add(&pairing::multiexp_g1(&pk.a_query, witness));
+    let b = pk.beta_g2.add(&pairing::multiexp_g2(&pk.b_query_g2, witness));
+    let private_witness = &witness[pk.num_public..];
+    let c = pairing::multiexp_g1(&pk.c_query, private_witness).add(&pairing::multiexp_g1(&pk.h_query, &h));
+
+    Ok(Proof { a, b, c })
+}
+
+/// Checks the single Groth16 pairing equation
+/// `e(A,B) = e(alpha,beta) * e(ic[0] + sum_i(public_inputs[i] * ic[i + 1]), gamma) * e(C,delta)`.
+pub fn verify(_backend: &InsecureDemoBackend, vk: &VerifyingKey, public_inputs: &[FieldElement], proof: &Proof) -> bool {
+    let mut ic_term = vk.ic[0].clone();
+    for (input, ic) in public_inputs.iter().zip(vk.ic.iter().skip(1)) {
+        ic_term = ic_term.add(&ic.scalar_mul(input));
+    }
+
+    let lhs = pairing::pairing(&proof.a, &proof.b);
+    let rhs = combine_gt(&[
+        pairing::pairing(&vk.alpha_g1, &vk.beta_g2),
+        pairing::pairing(&ic_term, &vk.gamma_g2),
+        pairing::pairing(&proof.c, &vk.delta_g2),
+    ]);
+
+    lhs == rhs
+}
+
+fn combine_gt(elements: &[GtElement]) -> GtElement {
+    elements.iter().fold(GtElement::identity(), |acc, e| acc.mul(e))
+}
+
+impl Proof {
+    /// Serializes the proof to a binary file.
+    pub fn save_to_binary(&self, filename: &str) -> Result<(), ZkError> {
+        let mut file = File::create(filename)?;
+        let encoded = bincode::serialize(self)?;
+        file.write_all(&encoded)?;
+        Ok(())
+    }
+}
+
+impl VerifyingKey {
+    /// Serializes the verifying key to a binary file.
+    pub fn save_to_binary(&self, filename: &str) -> Result<(), ZkError> {
+        let mut file = File::create(filename)?;
+        let encoded = bincode::serialize(self)?;
+        file.write_all(&encoded)?;
+        Ok(())
+    }
+
+    /// Loads a verifying key previously saved with `save_to_binary`.
+    pub fn load_from_binary(filename: &str) -> Result<Self, ZkError> {
+        let file = File::open(filename)?;
+        Ok(bincode::deserialize_from(file)?)
+    }
+}
@@ -1,72 +1,295 @@
+use crate::error::ZkError;
+use crate::field::FieldElement;
 use num_bigint::BigInt;
-use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
 use num_traits::Zero;
-use crate::field::FieldElement;
+use serde::{Deserialize, Serialize};
 
+/// A power-of-two multiplicative subgroup of the scalar field, used as the
+/// evaluation domain for the QAP. Modeled on bellman's `EvaluationDomain`.
 #[derive(Clone, Serialize, Deserialize)]
-pub struct Variable {
-    pub index: usize,
-    pub value: BigInt,
+pub struct EvaluationDomain {
+    pub m: usize,
+    pub omega: FieldElement,
+    pub omega_inv: FieldElement,
+    pub m_inv: FieldElement,
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct Polynomial {
-    coefficients: HashMap<usize, FieldElement>, // Coefficients keyed by variable index
-}
+impl EvaluationDomain {
+    /// Smallest power-of-two domain that can hold `n` points.
+    pub fn new(n: usize) -> Self {
+        let m = n.max(1).next_power_of_two();
+        let omega = FieldElement::root_of_unity(m as u64);
+        let omega_inv = omega.inverse();
+        let m_inv = FieldElement::new(BigInt::from(m as u64)).inverse();
+        EvaluationDomain { m, omega, omega_inv, m_inv }
+    }
 
-#[derive(Serialize, Deserialize)]
-pub struct QAP {
-    pub left: Polynomial,
-    pub right: Polynomial,
-    pub output: Polynomial,
-}
+    /// In-place radix-2 Cooley-Tukey FFT over `values`, which must have
+    /// length `self.m`.
+    pub fn fft(&self, values: &mut [FieldElement]) {
+        Self::butterfly(values, &self.omega);
+    }
 
-impl QAP {
-    pub fn new() -> Self {
-        QAP {
-            left: Polynomial::new(),
-            right: Polynomial::new(),
-            output: Polynomial::new(),
+    /// Inverse FFT: run the butterfly network with `omega_inv`, then scale
+    /// every coefficient by `m_inv`.
+    pub fn ifft(&self, values: &mut Vec<FieldElement>) {
+        Self::butterfly(values, &self.omega_inv);
+        for v in values.iter_mut() {
+            *v = v.mul(&self.m_inv);
         }
     }
 
-    pub fn add_constraint(&mut self, left_coeffs: &[(usize, FieldElement)], right_coeffs: &[(usize, FieldElement)], output_coeffs: &[(usize, FieldElement)], _modulus: &BigInt) {
-        for (index, coeff) in left_coeffs {
-            *self.left.coefficients.entry(*index).or_insert(FieldElement::new(BigInt::zero())) += coeff.clone();
+    fn butterfly(values: &mut [FieldElement], root: &FieldElement) {
+        let n = values.len();
+        if n <= 1 {
+            return;
         }
-        for (index, coeff) in right_coeffs {
-            *self.right.coefficients.entry(*index).or_insert(FieldElement::new(BigInt::zero())) += coeff.clone();
+
+        // Bit-reversal permutation.
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+            if i < j {
+                values.swap(i, j);
+            }
         }
-        for (index, coeff) in output_coeffs {
-            *self.output.coefficients.entry(*index).or_insert(FieldElement::new(BigInt::zero())) += coeff.clone();
+
+        // Butterfly stages with twiddle factors omega^k.
+        let mut len = 2;
+        while len <= n {
+            let w_len = root.pow(&BigInt::from((n / len) as u64));
+            let mut i = 0;
+            while i < n {
+                let mut w = FieldElement::one();
+                for k in 0..len / 2 {
+                    let u = values[i + k].clone();
+                    let v = values[i + k + len / 2].mul(&w);
+                    values[i + k] = u.add(&v);
+                    values[i + k + len / 2] = u.sub(&v);
+                    w = w.mul(&w_len);
+                }
+                i += len;
+            }
+            len <<= 1;
         }
     }
 
-    pub fn evaluate(&self, assignment: &Vec<FieldElement>) -> FieldElement {
-        let left_eval = self.left.evaluate(assignment);
-        let right_eval = self.right.evaluate(assignment);
-        let output_eval = self.output.evaluate(assignment);
+    /// The vanishing polynomial `Z(x) = x^m - 1` evaluated at `x`.
+    pub fn evaluate_vanishing(&self, x: &FieldElement) -> FieldElement {
+        x.pow(&BigInt::from(self.m as u64)).sub(&FieldElement::one())
+    }
 
-        // Return the evaluation result: left * right - output
-        left_eval.mul(&right_eval).sub(&output_eval)
+    /// The Lagrange basis polynomials for this domain, evaluated at `x`:
+    /// `coeffs[j]` is `L_j(x)`, where `L_j` is `1` at `omega^j` and `0` at
+    /// every other domain point. Uses the closed form for a roots-of-unity
+    /// domain, `L_j(x) = (x^m - 1) * omega^j / (m * (x - omega^j))`, so the
+    /// whole basis costs one `evaluate_vanishing` plus `O(m)` field ops
+    /// instead of an `ifft` per polynomial — callers that need many
+    /// polynomials evaluated at the same `x` (like
+    /// `QAP::variable_evaluations`) compute this once and reuse it instead
+    /// of paying for a fresh interpolation per polynomial.
+    pub fn lagrange_coefficients(&self, x: &FieldElement) -> Vec<FieldElement> {
+        let z_at_x = self.evaluate_vanishing(x);
+        let mut coeffs = Vec::with_capacity(self.m);
+        let mut omega_pow_j = FieldElement::one();
+        for _ in 0..self.m {
+            let denom = x.sub(&omega_pow_j);
+            coeffs.push(z_at_x.mul(&omega_pow_j).mul(&self.m_inv).mul(&denom.inverse()));
+            omega_pow_j = omega_pow_j.mul(&self.omega);
+        }
+        coeffs
     }
+
+    /// Divides `a(x)*b(x) - c(x)` by `Z(x) = x^m - 1` using a coset FFT
+    /// twice the size of this domain (the product has degree `< 2m`).
+    /// Returns `Err(ZkError::QuotientNotExact)` rather than panicking if
+    /// the division has a remainder, which happens exactly when `a`, `b`,
+    /// `c` came from a witness that fails some constraint.
+    pub fn divide_by_vanishing(
+        &self,
+        a: &[FieldElement],
+        b: &[FieldElement],
+        c: &[FieldElement],
+    ) -> Result<Vec<FieldElement>, ZkError> {
+        let coset_domain = EvaluationDomain::new(2 * self.m);
+        let coset_gen = FieldElement::new(BigInt::from(5u64));
+
+        let shift = |poly: &[FieldElement]| -> Vec<FieldElement> {
+            let mut padded = vec![FieldElement::zero(); coset_domain.m];
+            let mut power = FieldElement::one();
+            for (i, coeff) in poly.iter().enumerate() {
+                padded[i] = coeff.mul(&power);
+                power = power.mul(&coset_gen);
+            }
+            padded
+        };
+
+        let mut a_c = shift(a);
+        let mut b_c = shift(b);
+        let mut c_c = shift(c);
+        coset_domain.fft(&mut a_c);
+        coset_domain.fft(&mut b_c);
+        coset_domain.fft(&mut c_c);
+
+        // Z(x) evaluated at each coset point `coset_gen * omega^i`.
+        let gen_pow_m = coset_gen.pow(&BigInt::from(self.m as u64));
+        let omega_m = coset_domain.omega.pow(&BigInt::from(self.m as u64));
+        let mut omega_pow_m = FieldElement::one();
+        let mut h_evals = Vec::with_capacity(coset_domain.m);
+        for i in 0..coset_domain.m {
+            let z = gen_pow_m.mul(&omega_pow_m).sub(&FieldElement::one());
+            let numerator = a_c[i].mul(&b_c[i]).sub(&c_c[i]);
+            h_evals.push(numerator.mul(&z.inverse()));
+            if i + 1 < coset_domain.m {
+                omega_pow_m = omega_pow_m.mul(&omega_m);
+            }
+        }
+
+        coset_domain.ifft(&mut h_evals);
+
+        // Undo the coset shift to recover h's coefficients.
+        let coset_gen_inv = coset_gen.inverse();
+        let mut power = FieldElement::one();
+        for coeff in h_evals.iter_mut() {
+            *coeff = coeff.mul(&power);
+            power = power.mul(&coset_gen_inv);
+        }
+
+        for coeff in &h_evals[self.m..] {
+            if !coeff.get_value().is_zero() {
+                return Err(ZkError::QuotientNotExact);
+            }
+        }
+        h_evals.truncate(self.m);
+        Ok(h_evals)
+    }
+}
+
+/// A sparse linear combination of witness variables: `index -> coefficient`.
+type LinearCombination = Vec<(usize, FieldElement)>;
+
+/// The Quadratic Arithmetic Program for an R1CS instance: one `(L, R, O)`
+/// linear combination per constraint, interpolated over a power-of-two
+/// evaluation domain so that satisfaction reduces to `Z(x) | A(x)B(x) - C(x)`.
+#[derive(Serialize, Deserialize)]
+pub struct QAP {
+    left: Vec<LinearCombination>,
+    right: Vec<LinearCombination>,
+    output: Vec<LinearCombination>,
 }
 
-impl Polynomial {
+impl QAP {
     pub fn new() -> Self {
-        Polynomial { coefficients: HashMap::new() }
+        QAP { left: Vec::new(), right: Vec::new(), output: Vec::new() }
+    }
+
+    pub fn add_constraint(
+        &mut self,
+        left_coeffs: &[(usize, FieldElement)],
+        right_coeffs: &[(usize, FieldElement)],
+        output_coeffs: &[(usize, FieldElement)],
+        _modulus: &BigInt,
+    ) {
+        self.left.push(left_coeffs.to_vec());
+        self.right.push(right_coeffs.to_vec());
+        self.output.push(output_coeffs.to_vec());
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.left.len()
     }
 
-    pub fn add_term(&mut self, index: usize, coefficient: FieldElement) {
-        self.coefficients.insert(index, coefficient);
+    pub fn domain(&self) -> EvaluationDomain {
+        EvaluationDomain::new(self.num_constraints())
     }
 
+    fn eval_vector(rows: &[LinearCombination], assignment: &[FieldElement], m: usize) -> Vec<FieldElement> {
+        let mut evals = vec![FieldElement::zero(); m];
+        for (j, row) in rows.iter().enumerate() {
+            let mut acc = FieldElement::zero();
+            for (index, coeff) in row {
+                acc = acc.add(&coeff.mul(&assignment[*index]));
+            }
+            evals[j] = acc;
+        }
+        evals
+    }
+
+    /// Interpolates the per-constraint evaluation vectors for `A`, `B`, `C`
+    /// at `assignment` into coefficient-form polynomials over the domain.
+    pub fn interpolate(
+        &self,
+        assignment: &[FieldElement],
+    ) -> (EvaluationDomain, Vec<FieldElement>, Vec<FieldElement>, Vec<FieldElement>) {
+        let domain = self.domain();
+        let mut a = Self::eval_vector(&self.left, assignment, domain.m);
+        let mut b = Self::eval_vector(&self.right, assignment, domain.m);
+        let mut c = Self::eval_vector(&self.output, assignment, domain.m);
+        domain.ifft(&mut a);
+        domain.ifft(&mut b);
+        domain.ifft(&mut c);
+        (domain, a, b, c)
+    }
+
+    /// For every witness variable, evaluates its `A`, `B`, `C` QAP
+    /// polynomials (interpolated across all constraints) at `tau`. Used by
+    /// Groth16 `setup` to build the proving/verifying key query vectors.
+    ///
+    /// Computes the domain's Lagrange coefficients at `tau` once and folds
+    /// every constraint row's sparse terms through them, rather than
+    /// running a full `ifft` per variable per `A`/`B`/`C` — the latter is
+    /// `O(num_vars * m log m)`, which is intractable once `num_vars` and
+    /// `m` both scale with a circuit's constraint count.
+    pub fn variable_evaluations(
+        &self,
+        num_vars: usize,
+        tau: &FieldElement,
+    ) -> (Vec<FieldElement>, Vec<FieldElement>, Vec<FieldElement>) {
+        let domain = self.domain();
+        let lagrange = domain.lagrange_coefficients(tau);
+
+        let accumulate = |rows: &[LinearCombination]| -> Vec<FieldElement> {
+            let mut evals = vec![FieldElement::zero(); num_vars];
+            for (j, row) in rows.iter().enumerate() {
+                for (index, coeff) in row {
+                    evals[*index] = evals[*index].add(&coeff.mul(&lagrange[j]));
+                }
+            }
+            evals
+        };
+
+        (accumulate(&self.left), accumulate(&self.right), accumulate(&self.output))
+    }
+
+    /// Computes the quotient `h(x) = (A(x)B(x) - C(x)) / Z(x)`. Errors if
+    /// the witness doesn't satisfy every constraint exactly (non-zero
+    /// remainder) rather than panicking; callers that haven't already run
+    /// `R1CS::verify_witness` should expect this to fail on a bad witness.
+    pub fn quotient(&self, assignment: &[FieldElement]) -> Result<Vec<FieldElement>, ZkError> {
+        let (domain, a, b, c) = self.interpolate(assignment);
+        domain.divide_by_vanishing(&a, &b, &c)
+    }
+
+    /// Evaluates `A(x)*B(x) - C(x)` at the witness, summed over every
+    /// domain point, without dividing by the vanishing polynomial. A
+    /// satisfying witness gives zero, but per-constraint residuals can
+    /// cancel in the sum, so a zero result does not show that every
+    /// constraint holds; `quotient` is the exact check.
     pub fn evaluate(&self, assignment: &Vec<FieldElement>) -> FieldElement {
-        let mut result = FieldElement::new(BigInt::zero()); // Use the same modulus
-        for (index, coefficient) in &self.coefficients {
-            result = result.add(&coefficient.mul(&assignment[*index]));
+        let domain = self.domain();
+        let a = Self::eval_vector(&self.left, assignment, domain.m);
+        let b = Self::eval_vector(&self.right, assignment, domain.m);
+        let c = Self::eval_vector(&self.output, assignment, domain.m);
+
+        let mut acc = FieldElement::zero();
+        for j in 0..domain.m {
+            acc = acc.add(&a[j].mul(&b[j]).sub(&c[j]));
         }
-        result
+        acc
     }
 }
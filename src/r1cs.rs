@@ -1,10 +1,10 @@
+use crate::error::ZkError;
 use crate::field::FieldElement;
 use num_bigint::BigInt;
 use serde::{Serialize, Deserialize};
 use std::fs::File;
 use std::io::{Write};
 use num_traits::Zero;
-use crate::proof::Proof;
 use crate::qap::QAP;
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -33,6 +33,11 @@ pub struct R1CS {
     pub variables: Vec<Variable>,
     pub constraints: Vec<Constraint>,
     pub qap: QAP, // Adding QAP representation
+    /// Number of leading `variables` treated as public inputs; the rest
+    /// are private witness. Defaults to `0` (everything private) for
+    /// circuits built through `Circuit`, and is set by importers such as
+    /// `R1CS::from_circom_json` that know which variables are public.
+    pub num_public_inputs: usize,
 }
 
 impl R1CS {
@@ -41,12 +46,25 @@ impl R1CS {
             variables: Vec::new(),
             constraints: Vec::new(),
             qap: QAP::new(), // Initialize QAP
+            num_public_inputs: 0,
         }
     }
 
-    /// Adds a constraint to the R1CS and also updates the QAP representation.
+    /// Adds a constraint `left · right = output` (each side an arbitrary
+    /// sparse linear combination of witness variables) to the R1CS and
+    /// updates the QAP representation in lockstep.
     pub fn add_constraint(&mut self, left_coeffs: &[(usize, FieldElement)], right_coeffs: &[(usize, FieldElement)], output_coeffs: &[(usize, FieldElement)], modulus: &BigInt) {
         self.qap.add_constraint(left_coeffs, right_coeffs, output_coeffs, modulus);
+
+        let to_terms = |coeffs: &[(usize, FieldElement)]| -> Vec<(Variable, BigInt)> {
+            coeffs.iter().map(|(index, coeff)| (self.variables[*index].clone(), coeff.get_value())).collect()
+        };
+        self.constraints.push(Constraint {
+            left: to_terms(left_coeffs),
+            right: to_terms(right_coeffs),
+            output: to_terms(output_coeffs),
+            operation: Operation::Mul,
+        });
     }
 
     /// Generates a witness based on the variable values.
@@ -57,17 +75,15 @@ impl R1CS {
         }).collect()
     }
 
-    /// Generates a proof based on the current constraints and witness.
-    pub fn generate_proof(&self, witness: &Vec<FieldElement>) -> Proof {
-        // Simplified proof generation logic without hashes
-        Proof::generate_proof(self, witness) // Make sure Proof::generate_proof can handle FieldElement
-    }
-
-    /// Evaluates the QAP with the current witness.
-    pub fn evaluate_qap(&self) -> BigInt {
+    /// Evaluates the QAP with the current witness and returns the quotient
+    /// polynomial `h(x) = (A(x)B(x) - C(x)) / Z(x)` in coefficient form.
+    /// Checking that the division has no remainder (see
+    /// `QAP::divide_by_vanishing`) is itself the satisfaction check: a
+    /// witness that fails any constraint makes `A*B - C` non-vanishing on
+    /// the domain, so it isn't a multiple of `Z(x)`.
+    pub fn evaluate_qap(&self) -> Result<Vec<FieldElement>, ZkError> {
         let witness = self.generate_witness(); // Generates witness of type Vec<FieldElement>
-        let result = self.qap.evaluate(&witness); // result is of type FieldElement
-        result.get_value() // Assuming get_value() method retrieves BigInt from FieldElement
+        self.qap.quotient(&witness)
     }
 
     /// Adds a variable and returns its index.
@@ -78,48 +94,88 @@ impl R1CS {
     }
 
     /// Saves the R1CS to a binary file.
-    pub fn save_to_binary(&self, filename: &str) {
-        let mut file = File::create(filename).expect("Could not create proof file");
-        let encoded: Vec<u8> = bincode::serialize(&self).expect("Failed to serialize proof");
-        file.write_all(&encoded).expect("Failed to write proof to file");
+    pub fn save_to_binary(&self, filename: &str) -> Result<(), ZkError> {
+        let mut file = File::create(filename)?;
+        let encoded: Vec<u8> = bincode::serialize(&self)?;
+        file.write_all(&encoded)?;
+        Ok(())
     }
 
     /// Loads the R1CS from a binary file.
-    pub fn load_from_binary(filename: &str) -> Self {
-        let file = File::open(filename).expect("Could not open file");
-        let r1cs: R1CS = bincode::deserialize_from(file).expect("Failed to deserialize R1CS");
-        r1cs
+    pub fn load_from_binary(filename: &str) -> Result<Self, ZkError> {
+        let file = File::open(filename)?;
+        Ok(bincode::deserialize_from(file)?)
     }
 
-    pub fn verify_witness(&self, witness: &[FieldElement]) -> bool {
-        for constraint in &self.constraints {
-            let mut left_eval = FieldElement::new(BigInt::zero());
-            let mut right_eval = FieldElement::new(BigInt::zero());
+    /// Checks every constraint's R1CS relation `left · right = output`
+    /// against `witness`, where `left`, `right`, and `output` are each
+    /// evaluated as the dot product of their linear combination with the
+    /// witness. Returns the index of the first unsatisfied constraint
+    /// rather than a bare `false`, so a caller can report which gate of
+    /// the circuit the witness got wrong.
+    pub fn verify_witness(&self, witness: &[FieldElement]) -> Result<(), ZkError> {
+        if witness.len() != self.variables.len() {
+            return Err(ZkError::WitnessLengthMismatch { expected: self.variables.len(), actual: witness.len() });
+        }
 
-            // Evaluate the left side of the constraint
+        for (index, constraint) in self.constraints.iter().enumerate() {
+            let mut left_eval = FieldElement::new(BigInt::zero());
             for (var_index, coeff) in &constraint.left {
-                let var_value = &witness[var_index.index]; // Access the witness value using the index
-                left_eval = left_eval + (var_value.clone() * coeff); // Compute the left-hand side
+                let var_value = &witness[var_index.index];
+                left_eval = left_eval + (var_value.clone() * coeff);
             }
 
-            // Evaluate the right side of the constraint
+            let mut right_eval = FieldElement::new(BigInt::zero());
             for (var_index, coeff) in &constraint.right {
-                let var_value = &witness[var_index.index]; // Access the witness value using the index
-                right_eval = right_eval + (var_value.clone() * coeff); // Compute the right-hand side
+                let var_value = &witness[var_index.index];
+                right_eval = right_eval + (var_value.clone() * coeff);
             }
 
-            // Evaluate the output side of the constraint
             let mut output_eval = FieldElement::new(BigInt::zero());
             for (var_index, coeff) in &constraint.output {
-                let var_value = &witness[var_index.index]; // Access the witness value using the index
-                output_eval = output_eval + (var_value.clone() * coeff); // Compute the output side
+                let var_value = &witness[var_index.index];
+                output_eval = output_eval + (var_value.clone() * coeff);
             }
 
-            // Check if the constraint is satisfied
-            if left_eval != right_eval || right_eval != output_eval {
-                return false; // The witness does not satisfy this constraint
+            if left_eval.mul(&right_eval) != output_eval {
+                return Err(ZkError::UnsatisfiedConstraint { index });
             }
         }
-        true // All constraints satisfied
+        Ok(())
+    }
+}
+
+/// A thin builder over `R1CS`, modeled on the composer pattern seen in
+/// zkevm/plonk front-ends: callers allocate variables and push raw
+/// `left · right = output` constraints directly, rather than going through
+/// `Circuit`'s two fixed `Add`/`Mul` gate types.
+pub struct ConstraintSystem {
+    r1cs: R1CS,
+}
+
+impl ConstraintSystem {
+    pub fn new() -> Self {
+        ConstraintSystem { r1cs: R1CS::new() }
+    }
+
+    /// Allocates a new witness variable and returns its index.
+    pub fn alloc(&mut self, value: FieldElement) -> usize {
+        self.r1cs.add_variable(value)
+    }
+
+    /// Enforces `left · right = output`, where each side is an arbitrary
+    /// sparse linear combination of variables allocated with `alloc`.
+    pub fn enforce(
+        &mut self,
+        left: &[(usize, FieldElement)],
+        right: &[(usize, FieldElement)],
+        output: &[(usize, FieldElement)],
+    ) {
+        self.r1cs.add_constraint(left, right, output, &BigInt::zero());
+    }
+
+    /// Consumes the builder, returning the assembled `R1CS`.
+    pub fn into_r1cs(self) -> R1CS {
+        self.r1cs
     }
 }
@@ -0,0 +1,209 @@
+use crate::field::FieldElement;
+use crate::poseidon;
+use crate::r1cs::ConstraintSystem;
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+use std::collections::HashMap;
+
+/// Depth of the tree: keys are addressed by up to `DEPTH` bits, so the
+/// tree can hold `2^DEPTH` leaves without ever materializing them.
+pub const DEPTH: usize = 256;
+
+/// A lazy sparse Merkle tree, ported from the "big lazy tree" idea in
+/// ginger-lib: leaves are keyed by an arbitrary `BigInt` up to `DEPTH`
+/// bits, and any subtree that has never been written collapses to a
+/// precomputed "empty node" hash rather than being stored. Only nodes on
+/// a path that's actually been touched by `insert` live in `nodes`, so
+/// the map stays proportional to the number of insertions, not `2^DEPTH`.
+pub struct SparseMerkleTree {
+    /// `empty_hashes[l]` is the hash of an empty subtree of height `l`
+    /// (`empty_hashes[0]` is the empty leaf).
+    empty_hashes: Vec<FieldElement>,
+    /// Explicitly stored nodes, keyed by `(level, index at that level)`.
+    /// Absent entries are implicitly `empty_hashes[level]`.
+    nodes: HashMap<(usize, BigInt), FieldElement>,
+}
+
+impl SparseMerkleTree {
+    pub fn new() -> Self {
+        let mut empty_hashes = Vec::with_capacity(DEPTH + 1);
+        empty_hashes.push(FieldElement::zero());
+        for level in 1..=DEPTH {
+            let below = empty_hashes[level - 1].clone();
+            empty_hashes.push(poseidon::poseidon_hash(&[below.clone(), below]));
+        }
+        SparseMerkleTree { empty_hashes, nodes: HashMap::new() }
+    }
+
+    fn node_at(&self, level: usize, index: &BigInt) -> FieldElement {
+        self.nodes
+            .get(&(level, index.clone()))
+            .cloned()
+            .unwrap_or_else(|| self.empty_hashes[level].clone())
+    }
+
+    /// The index of `index`'s sibling at the same level: flipping the bit
+    /// of `key` that level's shift just exposed.
+    fn sibling_index(index: &BigInt) -> BigInt {
+        if (index % 2) == BigInt::zero() { index + 1 } else { index - 1 }
+    }
+
+    /// Writes `value` at `key`'s leaf and recomputes every ancestor hash
+    /// up to the root. Writing `empty_hashes[0]` is how `remove` is
+    /// implemented: it restores the leaf (and any ancestors that become
+    /// fully empty again) to the precomputed empty hash.
+    pub fn insert(&mut self, key: &BigInt, value: FieldElement) {
+        let mut index = key.clone();
+        let mut current = value;
+
+        for level in 0..DEPTH {
+            if current == self.empty_hashes[level] {
+                self.nodes.remove(&(level, index.clone()));
+            } else {
+                self.nodes.insert((level, index.clone()), current.clone());
+            }
+
+            let sibling = self.node_at(level, &Self::sibling_index(&index));
+            let is_left = (&index % 2) == BigInt::one();
+            current = if is_left {
+                poseidon::poseidon_hash(&[sibling, current])
+            } else {
+                poseidon::poseidon_hash(&[current, sibling])
+            };
+            index = index / 2;
+        }
+
+        if current == self.empty_hashes[DEPTH] {
+            self.nodes.remove(&(DEPTH, index));
+        } else {
+            self.nodes.insert((DEPTH, index), current);
+        }
+    }
+
+    /// Restores `key`'s leaf (and any now-empty ancestors) to the empty
+    /// hash, removing it from the tree.
+    pub fn remove(&mut self, key: &BigInt) {
+        self.insert(key, self.empty_hashes[0].clone());
+    }
+
+    pub fn root(&self) -> FieldElement {
+        self.node_at(DEPTH, &BigInt::zero())
+    }
+
+    /// The authentication path for `key`: sibling hash and `is_left` flag
+    /// (whether the sibling sits on the left of the pairing) at every
+    /// level from the leaf up to the root.
+    fn path(&self, key: &BigInt) -> Vec<(FieldElement, bool)> {
+        let mut index = key.clone();
+        let mut path = Vec::with_capacity(DEPTH);
+        for level in 0..DEPTH {
+            let sibling = self.node_at(level, &Self::sibling_index(&index));
+            let is_left = (&index % 2) == BigInt::one();
+            path.push((sibling, is_left));
+            index = index / 2;
+        }
+        path
+    }
+
+    /// Proves that `key` is present with its current leaf value: the
+    /// leaf value plus the authentication path up to the root.
+    pub fn membership_proof(&self, key: &BigInt) -> Option<(FieldElement, Vec<(FieldElement, bool)>)> {
+        let leaf = self.node_at(0, key);
+        if leaf == self.empty_hashes[0] {
+            return None;
+        }
+        Some((leaf, self.path(key)))
+    }
+
+    /// Proves that `key` is absent: the authentication path from the
+    /// empty-leaf hash at that position up to the root, letting a
+    /// verifier confirm the key currently holds no value (it was never
+    /// inserted, or was since removed) without seeing the rest of the tree.
+    pub fn non_membership_proof(&self, key: &BigInt) -> Option<Vec<(FieldElement, bool)>> {
+        let leaf = self.node_at(0, key);
+        if leaf != self.empty_hashes[0] {
+            return None;
+        }
+        Some(self.path(key))
+    }
+}
+
+/// Circuit-side path verifier, the sparse-tree analogue of the dense-tree
+/// walk in `main::merkle_tree_proof`: allocates the leaf and every
+/// sibling, folds them through `poseidon::poseidon_gadget` level by
+/// level, and enforces that the computed root matches `claimed_root`.
+/// Passing an empty-leaf `leaf_value` proves non-membership; any other
+/// value proves membership of that value at the path's key.
+pub fn verify_path_gadget(
+    cs: &mut ConstraintSystem,
+    one: usize,
+    leaf_value: FieldElement,
+    path: &[(FieldElement, bool)],
+    claimed_root: FieldElement,
+) {
+    let mut current_index = cs.alloc(leaf_value.clone());
+    let mut current_value = leaf_value;
+
+    for (sibling_value, is_left) in path {
+        let sibling_index = cs.alloc(sibling_value.clone());
+        let (new_index, new_value) = if *is_left {
+            poseidon::poseidon_gadget(cs, one, (sibling_index, sibling_value.clone()), (current_index, current_value))
+        } else {
+            poseidon::poseidon_gadget(cs, one, (current_index, current_value), (sibling_index, sibling_value.clone()))
+        };
+        current_index = new_index;
+        current_value = new_value;
+    }
+
+    cs.enforce(
+        &[(current_index, FieldElement::one())],
+        &[(one, FieldElement::one())],
+        &[(one, claimed_root)],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Recomputes the root `path` authenticates for `leaf`, the same fold
+    /// `verify_path_gadget` constrains in-circuit, without paying for a
+    /// `DEPTH = 256` R1CS: a full in-circuit check belongs in an
+    /// integration-style test, not a unit test run on every `cargo test`.
+    fn fold_path(leaf: FieldElement, path: &[(FieldElement, bool)]) -> FieldElement {
+        path.iter().fold(leaf, |current, (sibling, is_left)| {
+            if *is_left {
+                poseidon::poseidon_hash(&[sibling.clone(), current])
+            } else {
+                poseidon::poseidon_hash(&[current, sibling.clone()])
+            }
+        })
+    }
+
+    #[test]
+    fn membership_proof_verifies_and_non_membership_rejects_the_same_key() {
+        let mut tree = SparseMerkleTree::new();
+        let key = BigInt::from(7u64);
+        let value = FieldElement::new(BigInt::from(123u64));
+        tree.insert(&key, value.clone());
+
+        let (leaf, path) = tree.membership_proof(&key).expect("key was just inserted");
+        assert_eq!(leaf, value);
+        assert_eq!(fold_path(leaf, &path), tree.root());
+
+        // A present key has no non-membership proof.
+        assert!(tree.non_membership_proof(&key).is_none());
+    }
+
+    #[test]
+    fn non_membership_proof_verifies_for_an_absent_key() {
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(&BigInt::from(1u64), FieldElement::new(BigInt::from(1u64)));
+
+        let absent_key = BigInt::from(2u64);
+        assert!(tree.membership_proof(&absent_key).is_none());
+
+        let path = tree.non_membership_proof(&absent_key).expect("key was never inserted");
+        assert_eq!(fold_path(tree.empty_hashes[0].clone(), &path), tree.root());
+    }
+}